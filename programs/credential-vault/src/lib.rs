@@ -1,7 +1,57 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::keccak;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
 
 declare_id!("credvault1111111111111111111111111111");
 
+/// Fixed depth of every revocation Merkle tree. Bounds `verify_non_revocation`
+/// and the registry update instructions to O(depth) regardless of issuance volume.
+pub const REVOCATION_TREE_DEPTH: usize = 20;
+
+/// Fixed depth of the per-credential claims Merkle tree, matching the 64 bits
+/// addressable by `AccessGrantAccount::field_mask`.
+pub const CLAIM_TREE_DEPTH: usize = 6;
+
+/// A single claim field a holder is disclosing to a verifier, proven against
+/// the credential's `claims_hash` Merkle root without revealing any other field.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct DisclosedField {
+    pub index: u8,
+    pub value_hash: [u8; 32],
+}
+
+/// Lifetime of an access grant: either standing (never expires) or bounded
+/// by a concrete unix timestamp.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, PartialEq, Eq)]
+pub enum ExpiryMode {
+    Permanent,
+    ExpiresAt(i64),
+}
+
+/// Lifecycle state of a registered issuer.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, PartialEq, Eq)]
+pub enum IssuerStatus {
+    Active,
+    Suspended,
+    Revoked,
+}
+
+/// Auditable, one-shot status summary for an access grant, emitted by
+/// `introspect_access_grant` (OAuth token-introspection style).
+#[event]
+pub struct GrantIntrospection {
+    pub active: bool,
+    pub grantee: Pubkey,
+    pub field_mask: u64,
+    pub purpose: String,
+    pub expires_at: Option<i64>,
+    pub credential_revoked: bool,
+    pub credential_expired: bool,
+}
+
 #[program]
 pub mod credential_vault {
     use super::*;
@@ -14,8 +64,9 @@ pub mod credential_vault {
         issuer_did: String,
         expiry: Option<i64>,
         metadata_uri: String,
+        revocation_index: u32,
+        registry_id: String,
     ) -> Result<()> {
-        let credential = &mut ctx.accounts.credential;
         let clock = Clock::get()?;
 
         require!(
@@ -31,6 +82,30 @@ pub mod credential_vault {
             CredentialError::UriTooLong
         );
 
+        let issuer_registry = &ctx.accounts.issuer_registry;
+        require!(
+            issuer_registry.status == IssuerStatus::Active,
+            CredentialError::IssuerNotActive
+        );
+
+        require!(
+            revocation_index < ctx.accounts.revocation_registry.max_entries,
+            CredentialError::IndexOutOfRange
+        );
+
+        let message = issuer_signing_message(
+            &ctx.accounts.owner.key(),
+            &credential_type,
+            &claims_hash,
+            expiry,
+        );
+        verify_issuer_signature(
+            &ctx.accounts.instructions,
+            &issuer_registry.ed25519_pubkey,
+            &message,
+        )?;
+
+        let credential = &mut ctx.accounts.credential;
         credential.owner = ctx.accounts.owner.key();
         credential.credential_type = credential_type;
         credential.claims_hash = claims_hash;
@@ -40,6 +115,8 @@ pub mod credential_vault {
         credential.metadata_uri = metadata_uri;
         credential.revoked = false;
         credential.revocation_reason = None;
+        credential.revocation_index = revocation_index;
+        credential.revocation_registry = ctx.accounts.revocation_registry.key();
         credential.bump = ctx.bumps.credential;
 
         msg!(
@@ -74,7 +151,7 @@ pub mod credential_vault {
         ctx: Context<GrantAccess>,
         grantee: Pubkey,
         purpose: String,
-        expires_at: i64,
+        expiry: ExpiryMode,
         field_mask: u64,
     ) -> Result<()> {
         let access_grant = &mut ctx.accounts.access_grant;
@@ -84,16 +161,13 @@ pub mod credential_vault {
             purpose.len() <= 200,
             CredentialError::PurposeTooLong
         );
-        require!(
-            expires_at > clock.unix_timestamp,
-            CredentialError::InvalidExpiry
-        );
+        validate_expiry_mode(&expiry, clock.unix_timestamp)?;
 
         access_grant.credential = ctx.accounts.credential.key();
         access_grant.grantor = ctx.accounts.owner.key();
         access_grant.grantee = grantee;
         access_grant.purpose = purpose;
-        access_grant.expires_at = expires_at;
+        access_grant.expiry = expiry;
         access_grant.field_mask = field_mask;
         access_grant.revoked = false;
         access_grant.bump = ctx.bumps.access_grant;
@@ -107,6 +181,19 @@ pub mod credential_vault {
         Ok(())
     }
 
+    /// Extend or convert an existing access grant's lifetime. Only the
+    /// original grantor may renew it.
+    pub fn renew_access_grant(ctx: Context<RenewAccessGrant>, new_expiry: ExpiryMode) -> Result<()> {
+        let access_grant = &mut ctx.accounts.access_grant;
+        let clock = Clock::get()?;
+
+        validate_expiry_mode(&new_expiry, clock.unix_timestamp)?;
+        access_grant.expiry = new_expiry;
+
+        msg!("Access grant renewed for grantee: {}", access_grant.grantee);
+        Ok(())
+    }
+
     /// Revoke an access grant
     pub fn revoke_access_grant(ctx: Context<RevokeAccessGrant>) -> Result<()> {
         let access_grant = &mut ctx.accounts.access_grant;
@@ -142,9 +229,385 @@ pub mod credential_vault {
             CredentialError::HashMismatch
         );
 
+        // Verify the issuing DID is a currently-trusted issuer
+        require!(
+            ctx.accounts.issuer_registry.status == IssuerStatus::Active,
+            CredentialError::IssuerNotTrusted
+        );
+
         msg!("Credential verified: {}", credential.credential_type);
         Ok(())
     }
+
+    /// Register an issuer's Ed25519 signing key and trust status so
+    /// `issue_credential` can be bound to a real, verifiable signature.
+    pub fn register_issuer(
+        ctx: Context<RegisterIssuer>,
+        issuer_did: String,
+        ed25519_pubkey: [u8; 32],
+        status: IssuerStatus,
+    ) -> Result<()> {
+        let issuer = &mut ctx.accounts.issuer;
+
+        require!(issuer_did.len() <= 100, CredentialError::DidTooLong);
+
+        issuer.authority = ctx.accounts.authority.key();
+        issuer.issuer_did = issuer_did;
+        issuer.ed25519_pubkey = ed25519_pubkey;
+        issuer.status = status;
+        issuer.bump = ctx.bumps.issuer;
+
+        msg!("Issuer registered: {}", issuer.issuer_did);
+        Ok(())
+    }
+
+    /// Update an issuer's trust status (e.g. suspend or revoke).
+    pub fn update_issuer_status(
+        ctx: Context<UpdateIssuerStatus>,
+        status: IssuerStatus,
+    ) -> Result<()> {
+        let issuer = &mut ctx.accounts.issuer;
+        issuer.status = status;
+
+        msg!("Issuer {} status updated", issuer.issuer_did);
+        Ok(())
+    }
+
+    /// Initialize a revocation registry for an issuer. The registry tracks a
+    /// Merkle root over `2^REVOCATION_TREE_DEPTH` leaves, each `0` (valid) or
+    /// `1` (revoked), so a verifier can check non-revocation in O(depth)
+    /// without ever scanning credentials.
+    pub fn initialize_revocation_registry(
+        ctx: Context<InitializeRevocationRegistry>,
+        issuer_did: String,
+        registry_id: String,
+        max_entries: u32,
+        initial_root: [u8; 32],
+    ) -> Result<()> {
+        let registry = &mut ctx.accounts.registry;
+
+        require!(issuer_did.len() <= 100, CredentialError::DidTooLong);
+        require!(registry_id.len() <= 50, CredentialError::RegistryIdTooLong);
+        require!(
+            max_entries <= (1u64 << REVOCATION_TREE_DEPTH) as u32,
+            CredentialError::RegistryTooLarge
+        );
+
+        registry.authority = ctx.accounts.authority.key();
+        registry.issuer_did = issuer_did;
+        registry.registry_id = registry_id;
+        registry.root = initial_root;
+        registry.max_entries = max_entries;
+        registry.current_root_version = 0;
+        registry.bump = ctx.bumps.registry;
+
+        msg!("Revocation registry initialized: {}", registry.registry_id);
+        Ok(())
+    }
+
+    /// Mark `index` as revoked (leaf `1`). The issuer supplies the sibling
+    /// path for `index`; the program recomputes the root assuming the leaf is
+    /// currently valid (`0`), rejects the update if that doesn't match the
+    /// stored root, then stores the root recomputed with the leaf flipped.
+    pub fn revoke_by_index(
+        ctx: Context<UpdateRevocationRegistry>,
+        index: u32,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        update_leaf(&mut ctx.accounts.registry, index, &proof, 0, 1)?;
+        msg!("Revoked index {} in registry {}", index, ctx.accounts.registry.registry_id);
+        Ok(())
+    }
+
+    /// Clear a revocation (leaf `0`), the inverse of `revoke_by_index`.
+    pub fn unrevoke_by_index(
+        ctx: Context<UpdateRevocationRegistry>,
+        index: u32,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        update_leaf(&mut ctx.accounts.registry, index, &proof, 1, 0)?;
+        msg!("Unrevoked index {} in registry {}", index, ctx.accounts.registry.registry_id);
+        Ok(())
+    }
+
+    /// Prove that `index` is currently valid (not revoked) against the
+    /// registry's stored root, without revealing any other leaf.
+    pub fn verify_non_revocation(
+        ctx: Context<VerifyNonRevocation>,
+        index: u32,
+        leaf_value: u8,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        require!(leaf_value == 0, CredentialError::CredentialRevokedInRegistry);
+        require!(
+            proof.len() == REVOCATION_TREE_DEPTH,
+            CredentialError::InvalidProofLength
+        );
+        require!(
+            index < ctx.accounts.registry.max_entries,
+            CredentialError::IndexOutOfRange
+        );
+
+        let computed_root = merkle_root(index, leaf_value, &proof);
+        require!(
+            computed_root == ctx.accounts.registry.root,
+            CredentialError::RootMismatch
+        );
+
+        msg!("Non-revocation verified for index {}", index);
+        Ok(())
+    }
+
+    /// Prove that exactly the fields authorized by an access grant's
+    /// `field_mask` are being disclosed, each against the credential's
+    /// `claims_hash` Merkle root. Fails if a disclosed field isn't authorized,
+    /// or if an authorized field is missing from `disclosed`.
+    pub fn verify_disclosed_claims(
+        ctx: Context<VerifyDisclosedClaims>,
+        disclosed: Vec<DisclosedField>,
+        proofs: Vec<Vec<[u8; 32]>>,
+    ) -> Result<()> {
+        require!(
+            disclosed.len() == proofs.len(),
+            CredentialError::InvalidProofLength
+        );
+
+        let credential = &ctx.accounts.credential;
+        let access_grant = &ctx.accounts.access_grant;
+        let clock = Clock::get()?;
+
+        require!(!access_grant.revoked, CredentialError::AccessGrantRevoked);
+        require!(
+            !is_grant_expired(&access_grant.expiry, clock.unix_timestamp),
+            CredentialError::GrantExpired
+        );
+
+        require!(!credential.revoked, CredentialError::CredentialRevoked);
+        if let Some(expiry) = credential.expiry {
+            require!(expiry > clock.unix_timestamp, CredentialError::CredentialExpired);
+        }
+
+        let mut proven_mask: u64 = 0;
+
+        for (field, proof) in disclosed.iter().zip(proofs.iter()) {
+            require!(
+                proof.len() == CLAIM_TREE_DEPTH,
+                CredentialError::InvalidProofLength
+            );
+            require!((field.index as usize) < 64, CredentialError::IndexOutOfRange);
+
+            let bit = 1u64 << field.index;
+            require!(
+                access_grant.field_mask & bit != 0,
+                CredentialError::FieldNotAuthorized
+            );
+
+            let leaf_hash = keccak::hashv(&[&[field.index], &field.value_hash]).to_bytes();
+            let computed_root =
+                merkle_root_from_leaf_hash(field.index as u32, leaf_hash, proof);
+            require!(
+                computed_root == credential.claims_hash,
+                CredentialError::RootMismatch
+            );
+
+            proven_mask |= bit;
+        }
+
+        require!(
+            proven_mask == access_grant.field_mask,
+            CredentialError::MissingRequiredField
+        );
+
+        msg!("Disclosed claims verified for grantee: {}", access_grant.grantee);
+        Ok(())
+    }
+
+    /// One-shot, auditable status check for an access grant: emits a
+    /// `GrantIntrospection` event summarizing whether it's currently usable
+    /// and what it authorizes, so a relying party doesn't have to reconstruct
+    /// validity from raw account fields itself.
+    pub fn introspect_access_grant(ctx: Context<IntrospectAccessGrant>) -> Result<()> {
+        let credential = &ctx.accounts.credential;
+        let access_grant = &ctx.accounts.access_grant;
+        let clock = Clock::get()?;
+
+        let credential_expired = credential
+            .expiry
+            .map_or(false, |expiry| expiry <= clock.unix_timestamp);
+        let grant_expired = is_grant_expired(&access_grant.expiry, clock.unix_timestamp);
+
+        let active = !access_grant.revoked
+            && !credential.revoked
+            && !credential_expired
+            && !grant_expired;
+
+        let expires_at = match access_grant.expiry {
+            ExpiryMode::Permanent => None,
+            ExpiryMode::ExpiresAt(ts) => Some(ts),
+        };
+
+        emit!(GrantIntrospection {
+            active,
+            grantee: access_grant.grantee,
+            field_mask: access_grant.field_mask,
+            purpose: access_grant.purpose.clone(),
+            expires_at,
+            credential_revoked: credential.revoked,
+            credential_expired,
+        });
+
+        Ok(())
+    }
+}
+
+/// Reject a bounded expiry that's already in the past; `Permanent` is always valid.
+fn validate_expiry_mode(expiry: &ExpiryMode, now: i64) -> Result<()> {
+    if let ExpiryMode::ExpiresAt(ts) = expiry {
+        require!(*ts > now, CredentialError::InvalidExpiry);
+    }
+    Ok(())
+}
+
+/// Whether an access grant's lifetime has ended as of `now`. `Permanent`
+/// grants never expire.
+fn is_grant_expired(expiry: &ExpiryMode, now: i64) -> bool {
+    match expiry {
+        ExpiryMode::Permanent => false,
+        ExpiryMode::ExpiresAt(ts) => now >= *ts,
+    }
+}
+
+/// Build the byte message an issuer signs over when issuing a credential:
+/// `(owner || credential_type || claims_hash || expiry)`.
+fn issuer_signing_message(
+    owner: &Pubkey,
+    credential_type: &str,
+    claims_hash: &[u8; 32],
+    expiry: Option<i64>,
+) -> Vec<u8> {
+    let mut message = Vec::with_capacity(32 + credential_type.len() + 32 + 9);
+    message.extend_from_slice(owner.as_ref());
+    message.extend_from_slice(credential_type.as_bytes());
+    message.extend_from_slice(claims_hash);
+    match expiry {
+        Some(ts) => {
+            message.push(1);
+            message.extend_from_slice(&ts.to_le_bytes());
+        }
+        None => message.push(0),
+    }
+    message
+}
+
+/// Verify that the instruction immediately preceding this one in the same
+/// transaction is an Ed25519 program instruction signing `message` with
+/// `expected_pubkey`, per the Solana Ed25519 program's instruction data
+/// layout (a signature-offsets table followed by the signed data).
+fn verify_issuer_signature(
+    instructions_sysvar: &AccountInfo,
+    expected_pubkey: &[u8; 32],
+    message: &[u8],
+) -> Result<()> {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    require!(current_index > 0, CredentialError::MissingIssuerSignature);
+
+    let ed25519_ix = load_instruction_at_checked((current_index - 1) as usize, instructions_sysvar)?;
+    require!(
+        ed25519_ix.program_id == ed25519_program::ID,
+        CredentialError::MissingIssuerSignature
+    );
+
+    let data = &ed25519_ix.data;
+    require!(data.len() >= 16, CredentialError::InvalidIssuerSignature);
+    require!(data[0] == 1, CredentialError::InvalidIssuerSignature);
+
+    // Every *_instruction_index must point back at this same Ed25519
+    // instruction (the sentinel `u16::MAX` means "current instruction"),
+    // otherwise the pubkey/message bytes we're about to compare are decoys
+    // and the signature the native program actually checks covers different,
+    // attacker-chosen data living in some other instruction.
+    let signature_instruction_index = u16::from_le_bytes([data[4], data[5]]);
+    let public_key_instruction_index = u16::from_le_bytes([data[8], data[9]]);
+    let message_instruction_index = u16::from_le_bytes([data[14], data[15]]);
+    require!(
+        signature_instruction_index == u16::MAX
+            && public_key_instruction_index == u16::MAX
+            && message_instruction_index == u16::MAX,
+        CredentialError::InvalidIssuerSignature
+    );
+
+    let pubkey_offset = u16::from_le_bytes([data[6], data[7]]) as usize;
+    let message_data_offset = u16::from_le_bytes([data[10], data[11]]) as usize;
+    let message_data_size = u16::from_le_bytes([data[12], data[13]]) as usize;
+
+    require!(
+        data.len() >= pubkey_offset + 32,
+        CredentialError::InvalidIssuerSignature
+    );
+    require!(
+        &data[pubkey_offset..pubkey_offset + 32] == expected_pubkey,
+        CredentialError::InvalidIssuerSignature
+    );
+
+    require!(
+        data.len() >= message_data_offset + message_data_size,
+        CredentialError::InvalidIssuerSignature
+    );
+    require!(
+        &data[message_data_offset..message_data_offset + message_data_size] == message,
+        CredentialError::InvalidIssuerSignature
+    );
+
+    Ok(())
+}
+
+/// Recompute the registry root after flipping the leaf at `index` from
+/// `expected_old_leaf` to `new_leaf`, rejecting the update if the root
+/// computed from `expected_old_leaf` doesn't match what's currently stored.
+fn update_leaf(
+    registry: &mut Account<RevocationRegistryAccount>,
+    index: u32,
+    proof: &[[u8; 32]],
+    expected_old_leaf: u8,
+    new_leaf: u8,
+) -> Result<()> {
+    require!(
+        proof.len() == REVOCATION_TREE_DEPTH,
+        CredentialError::InvalidProofLength
+    );
+    require!(
+        index < registry.max_entries,
+        CredentialError::IndexOutOfRange
+    );
+
+    let old_root = merkle_root(index, expected_old_leaf, proof);
+    require!(old_root == registry.root, CredentialError::RootMismatch);
+
+    registry.root = merkle_root(index, new_leaf, proof);
+    registry.current_root_version = registry
+        .current_root_version
+        .checked_add(1)
+        .ok_or(CredentialError::ArithmeticOverflow)?;
+    Ok(())
+}
+
+/// Hash a leaf up to the Merkle root along `proof`, using `index`'s bits to
+/// decide, at each level, whether the running hash is the left or right child.
+fn merkle_root(index: u32, leaf_value: u8, proof: &[[u8; 32]]) -> [u8; 32] {
+    merkle_root_from_leaf_hash(index, keccak::hash(&[leaf_value]).to_bytes(), proof)
+}
+
+/// Hash an already-computed leaf hash up to the Merkle root along `proof`.
+fn merkle_root_from_leaf_hash(index: u32, leaf_hash: [u8; 32], proof: &[[u8; 32]]) -> [u8; 32] {
+    let mut node = leaf_hash;
+    for (level, sibling) in proof.iter().enumerate() {
+        node = if (index >> level) & 1 == 0 {
+            keccak::hashv(&[&node, sibling]).to_bytes()
+        } else {
+            keccak::hashv(&[sibling, &node]).to_bytes()
+        };
+    }
+    node
 }
 
 // ============ Account Structs ============
@@ -154,6 +617,8 @@ pub mod credential_vault {
     credential_type: String,
     issuer_did: String,
     metadata_uri: String,
+    revocation_index: u32,
+    registry_id: String,
 )]
 pub struct IssueCredential<'info> {
     #[account(
@@ -173,6 +638,27 @@ pub struct IssueCredential<'info> {
     /// Owner of the credential
     pub owner: Signer<'info>,
 
+    #[account(
+        seeds = [b"issuer", issuer_did.as_bytes()],
+        bump = issuer_registry.bump
+    )]
+    pub issuer_registry: Account<'info, IssuerAccount>,
+
+    #[account(
+        seeds = [
+            b"revocation-registry",
+            issuer_did.as_bytes(),
+            registry_id.as_bytes()
+        ],
+        bump = revocation_registry.bump
+    )]
+    pub revocation_registry: Account<'info, RevocationRegistryAccount>,
+
+    /// CHECK: validated by address constraint; read to find the preceding
+    /// Ed25519 program instruction that signs this issuance.
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
     #[account(mut)]
     pub payer: Signer<'info>,
 
@@ -250,6 +736,24 @@ pub struct RevokeAccessGrant<'info> {
     pub grantor: Signer<'info>,
 }
 
+#[derive(Accounts)]
+pub struct RenewAccessGrant<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"access",
+            access_grant.credential.as_ref(),
+            access_grant.grantor.as_ref(),
+            access_grant.purpose.as_bytes()
+        ],
+        bump = access_grant.bump,
+        has_one = grantor
+    )]
+    pub access_grant: Account<'info, AccessGrantAccount>,
+
+    pub grantor: Signer<'info>,
+}
+
 #[derive(Accounts)]
 pub struct VerifyCredential<'info> {
     #[account(
@@ -262,6 +766,147 @@ pub struct VerifyCredential<'info> {
         bump = credential.bump
     )]
     pub credential: Account<'info, CredentialAccount>,
+
+    #[account(
+        seeds = [b"issuer", credential.issuer_did.as_bytes()],
+        bump = issuer_registry.bump
+    )]
+    pub issuer_registry: Account<'info, IssuerAccount>,
+}
+
+#[derive(Accounts)]
+#[instruction(issuer_did: String)]
+pub struct RegisterIssuer<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + IssuerAccount::INIT_SPACE,
+        seeds = [b"issuer", issuer_did.as_bytes()],
+        bump
+    )]
+    pub issuer: Account<'info, IssuerAccount>,
+
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateIssuerStatus<'info> {
+    #[account(
+        mut,
+        seeds = [b"issuer", issuer.issuer_did.as_bytes()],
+        bump = issuer.bump,
+        has_one = authority @ CredentialError::Unauthorized
+    )]
+    pub issuer: Account<'info, IssuerAccount>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(issuer_did: String, registry_id: String)]
+pub struct InitializeRevocationRegistry<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + RevocationRegistryAccount::INIT_SPACE,
+        seeds = [b"revocation-registry", issuer_did.as_bytes(), registry_id.as_bytes()],
+        bump
+    )]
+    pub registry: Account<'info, RevocationRegistryAccount>,
+
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateRevocationRegistry<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"revocation-registry",
+            registry.issuer_did.as_bytes(),
+            registry.registry_id.as_bytes()
+        ],
+        bump = registry.bump,
+        has_one = authority @ CredentialError::Unauthorized
+    )]
+    pub registry: Account<'info, RevocationRegistryAccount>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyDisclosedClaims<'info> {
+    #[account(
+        seeds = [
+            b"credential",
+            credential.owner.as_ref(),
+            credential.credential_type.as_bytes(),
+            credential.issuer_did.as_bytes()
+        ],
+        bump = credential.bump
+    )]
+    pub credential: Account<'info, CredentialAccount>,
+
+    #[account(
+        seeds = [
+            b"access",
+            access_grant.credential.as_ref(),
+            access_grant.grantor.as_ref(),
+            access_grant.purpose.as_bytes()
+        ],
+        bump = access_grant.bump,
+        constraint = access_grant.credential == credential.key() @ CredentialError::GrantCredentialMismatch
+    )]
+    pub access_grant: Account<'info, AccessGrantAccount>,
+}
+
+#[derive(Accounts)]
+pub struct IntrospectAccessGrant<'info> {
+    #[account(
+        seeds = [
+            b"credential",
+            credential.owner.as_ref(),
+            credential.credential_type.as_bytes(),
+            credential.issuer_did.as_bytes()
+        ],
+        bump = credential.bump
+    )]
+    pub credential: Account<'info, CredentialAccount>,
+
+    #[account(
+        seeds = [
+            b"access",
+            access_grant.credential.as_ref(),
+            access_grant.grantor.as_ref(),
+            access_grant.purpose.as_bytes()
+        ],
+        bump = access_grant.bump,
+        constraint = access_grant.credential == credential.key() @ CredentialError::GrantCredentialMismatch
+    )]
+    pub access_grant: Account<'info, AccessGrantAccount>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyNonRevocation<'info> {
+    #[account(
+        seeds = [
+            b"revocation-registry",
+            registry.issuer_did.as_bytes(),
+            registry.registry_id.as_bytes()
+        ],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, RevocationRegistryAccount>,
 }
 
 // ============ State Accounts ============
@@ -271,6 +916,8 @@ pub struct VerifyCredential<'info> {
 pub struct CredentialAccount {
     pub owner: Pubkey,
     pub credential_type: String,
+    /// Merkle root over individual claim fields (leaf `i` = `keccak(i || value_hash_i)`),
+    /// checked field-by-field by `verify_disclosed_claims`.
     pub claims_hash: [u8; 32],
     pub issuer_did: String,
     pub issued_at: i64,
@@ -278,6 +925,34 @@ pub struct CredentialAccount {
     pub metadata_uri: String,
     pub revoked: bool,
     pub revocation_reason: Option<String>,
+    pub revocation_index: u32,
+    /// The `RevocationRegistryAccount` whose tree `revocation_index` is a leaf of.
+    pub revocation_registry: Pubkey,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct IssuerAccount {
+    pub authority: Pubkey,
+    #[max_len(100)]
+    pub issuer_did: String,
+    pub ed25519_pubkey: [u8; 32],
+    pub status: IssuerStatus,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct RevocationRegistryAccount {
+    pub authority: Pubkey,
+    #[max_len(100)]
+    pub issuer_did: String,
+    #[max_len(50)]
+    pub registry_id: String,
+    pub root: [u8; 32],
+    pub max_entries: u32,
+    pub current_root_version: u64,
     pub bump: u8,
 }
 
@@ -288,7 +963,7 @@ pub struct AccessGrantAccount {
     pub grantor: Pubkey,
     pub grantee: Pubkey,
     pub purpose: String,
-    pub expires_at: i64,
+    pub expiry: ExpiryMode,
     pub field_mask: u64,
     pub revoked: bool,
     pub bump: u8,
@@ -324,4 +999,136 @@ pub enum CredentialError {
 
     #[msg("Claims hash mismatch")]
     HashMismatch,
+
+    #[msg("Registry ID is too long (max 50 chars)")]
+    RegistryIdTooLong,
+
+    #[msg("Registry cannot hold more entries than the tree depth allows")]
+    RegistryTooLarge,
+
+    #[msg("Merkle proof count or length does not match the expected tree depth")]
+    InvalidProofLength,
+
+    #[msg("Index is out of range for the revocation registry or claim tree")]
+    IndexOutOfRange,
+
+    #[msg("Recomputed Merkle root does not match the stored root")]
+    RootMismatch,
+
+    #[msg("Credential index is revoked in the revocation registry")]
+    CredentialRevokedInRegistry,
+
+    #[msg("Signer is not the account's authority")]
+    Unauthorized,
+
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+
+    #[msg("Disclosed field is not authorized by the access grant's field mask")]
+    FieldNotAuthorized,
+
+    #[msg("An authorized field in the access grant's field mask was not proven")]
+    MissingRequiredField,
+
+    #[msg("Access grant does not reference this credential")]
+    GrantCredentialMismatch,
+
+    #[msg("Issuer is not currently active")]
+    IssuerNotActive,
+
+    #[msg("Credential's issuer is not a trusted, active issuer")]
+    IssuerNotTrusted,
+
+    #[msg("No preceding Ed25519 program instruction found for issuer signature")]
+    MissingIssuerSignature,
+
+    #[msg("Ed25519 instruction does not match the expected issuer signature")]
+    InvalidIssuerSignature,
+
+    #[msg("Access grant has expired")]
+    GrantExpired,
+
+    #[msg("Access grant has been revoked")]
+    AccessGrantRevoked,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaf_hash(value: u8) -> [u8; 32] {
+        keccak::hash(&[value]).to_bytes()
+    }
+
+    /// Hand-build a depth-3 (8-leaf) tree from `leaves` and return its root
+    /// alongside the sibling proof for `index`, independently of
+    /// `merkle_root`/`merkle_root_from_leaf_hash`, so the two can be checked
+    /// against each other.
+    fn build_tree(leaves: &[u8; 8], index: usize) -> ([u8; 32], Vec<[u8; 32]>) {
+        let mut level: Vec<[u8; 32]> = leaves.iter().map(|l| leaf_hash(*l)).collect();
+        let mut proof = Vec::new();
+        let mut idx = index;
+        while level.len() > 1 {
+            proof.push(level[idx ^ 1]);
+            level = level
+                .chunks(2)
+                .map(|pair| keccak::hashv(&[&pair[0], &pair[1]]).to_bytes())
+                .collect();
+            idx /= 2;
+        }
+        (level[0], proof)
+    }
+
+    #[test]
+    fn merkle_root_matches_hand_built_tree() {
+        let leaves = [0u8; 8];
+        let (root, proof) = build_tree(&leaves, 3);
+        assert_eq!(merkle_root(3, 0, &proof), root);
+    }
+
+    #[test]
+    fn merkle_root_revoke_then_unrevoke_round_trips() {
+        let leaves = [0u8; 8];
+        let (root, proof) = build_tree(&leaves, 5);
+
+        let revoked_root = merkle_root(5, 1, &proof);
+        assert_ne!(revoked_root, root);
+
+        let restored_root = merkle_root(5, 0, &proof);
+        assert_eq!(restored_root, root);
+    }
+
+    #[test]
+    fn merkle_root_rejects_proof_for_wrong_index() {
+        // A uniform tree can't tell positions apart (every sibling equals the
+        // node it pairs with), so revoke one leaf to make the layout matter.
+        let mut leaves = [0u8; 8];
+        leaves[3] = 1;
+        let (root, proof) = build_tree(&leaves, 2);
+        assert_eq!(merkle_root(2, 0, &proof), root);
+
+        // Replaying the same proof against a different index yields a root
+        // that no longer matches, which is how callers like `update_leaf`
+        // detect a stale or mismatched proof via `root == registry.root`.
+        assert_ne!(merkle_root(6, 0, &proof), root);
+    }
+
+    #[test]
+    fn merkle_root_rejects_tampered_proof_sibling() {
+        let leaves = [0u8; 8];
+        let (root, mut proof) = build_tree(&leaves, 4);
+
+        proof[0] = leaf_hash(99);
+        assert_ne!(merkle_root(4, 0, &proof), root);
+    }
+
+    #[test]
+    fn merkle_root_from_leaf_hash_matches_merkle_root() {
+        let leaves = [0u8; 8];
+        let (_, proof) = build_tree(&leaves, 1);
+        assert_eq!(
+            merkle_root_from_leaf_hash(1, leaf_hash(0), &proof),
+            merkle_root(1, 0, &proof)
+        );
+    }
 }
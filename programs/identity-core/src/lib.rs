@@ -2,6 +2,14 @@ use anchor_lang::prelude::*;
 
 declare_id!("idcore1111111111111111111111111111111111");
 
+/// Maximum number of guardians a recovery config may list. Bounds account
+/// space and keeps the linear guardian/approval scans cheap.
+pub const MAX_GUARDIANS: usize = 10;
+
+/// Default safety delay before a guardian-approved recovery can finalize,
+/// matching the window the old fixed-timeout recovery used.
+pub const DEFAULT_RECOVERY_TIMELOCK: i64 = 30 * 24 * 60 * 60;
+
 #[program]
 pub mod identity_core {
     use super::*;
@@ -53,9 +61,7 @@ pub mod identity_core {
     ) -> Result<()> {
         let identity = &mut ctx.accounts.identity;
 
-        require!(bit_index < 32, IdentityError::InvalidBitIndex);
-
-        identity.verification_bits |= 1 << bit_index;
+        identity.verification_bits = checked_set_bit(identity.verification_bits, bit_index)?;
 
         msg!(
             "Verification bit {} set for identity: {}",
@@ -72,9 +78,7 @@ pub mod identity_core {
     ) -> Result<()> {
         let identity = &mut ctx.accounts.identity;
 
-        require!(bit_index < 32, IdentityError::InvalidBitIndex);
-
-        identity.verification_bits &= !(1 << bit_index);
+        identity.verification_bits = checked_clear_bit(identity.verification_bits, bit_index)?;
 
         msg!(
             "Verification bit {} cleared for identity: {}",
@@ -84,32 +88,181 @@ pub mod identity_core {
         Ok(())
     }
 
-    /// Recover identity with new owner (after recovery period)
-    pub fn recover_identity(
-        ctx: Context<RecoverIdentity>,
-        new_owner: Pubkey,
+    /// Configure the guardian set and approval threshold used for social
+    /// recovery of this identity. `timelock_seconds` is the safety delay,
+    /// counted from when a proposal is opened, before it can be finalized;
+    /// defaults to `DEFAULT_RECOVERY_TIMELOCK` when `None`.
+    pub fn configure_guardians(
+        ctx: Context<ConfigureGuardians>,
+        guardians: Vec<Pubkey>,
+        threshold: u8,
+        timelock_seconds: Option<i64>,
     ) -> Result<()> {
-        let identity = &mut ctx.accounts.identity;
+        let config = &mut ctx.accounts.recovery_config;
+
+        require!(!guardians.is_empty(), IdentityError::NoGuardians);
+        require!(
+            guardians.len() <= MAX_GUARDIANS,
+            IdentityError::TooManyGuardians
+        );
+        require!(
+            threshold >= 1 && (threshold as usize) <= guardians.len(),
+            IdentityError::InvalidThreshold
+        );
+        for (i, guardian) in guardians.iter().enumerate() {
+            require!(
+                !guardians[..i].contains(guardian),
+                IdentityError::DuplicateGuardian
+            );
+        }
+
+        config.identity = ctx.accounts.identity.key();
+        config.guardians = guardians;
+        config.threshold = threshold;
+        config.timelock_seconds = timelock_seconds.unwrap_or(DEFAULT_RECOVERY_TIMELOCK);
+        config.bump = ctx.bumps.recovery_config;
+
+        msg!(
+            "Guardians configured for identity: {} (threshold {})",
+            config.identity,
+            config.threshold
+        );
+        Ok(())
+    }
+
+    /// Open a recovery proposal naming a new owner. Callable by any
+    /// configured guardian.
+    pub fn propose_recovery(ctx: Context<ProposeRecovery>, new_owner: Pubkey) -> Result<()> {
+        let config = &ctx.accounts.recovery_config;
+        let proposal = &mut ctx.accounts.proposal;
         let clock = Clock::get()?;
 
-        // Simple recovery: require 30 days since last update
-        let recovery_period = 30 * 24 * 60 * 60;
-        let elapsed = clock.unix_timestamp - identity.updated_at;
+        require!(
+            config.guardians.contains(&ctx.accounts.proposer.key()),
+            IdentityError::NotAGuardian
+        );
+
+        proposal.identity = ctx.accounts.identity.key();
+        proposal.new_owner = new_owner;
+        proposal.proposed_at = clock.unix_timestamp;
+        proposal.approvals = Vec::new();
+        proposal.bump = ctx.bumps.proposal;
+
+        msg!("Recovery proposed for identity: {} -> {}", proposal.identity, new_owner);
+        Ok(())
+    }
+
+    /// Approve the active recovery proposal. Each guardian may approve once.
+    pub fn approve_recovery(ctx: Context<ApproveRecovery>) -> Result<()> {
+        let config = &ctx.accounts.recovery_config;
+        let proposal = &mut ctx.accounts.proposal;
+        let guardian = ctx.accounts.guardian.key();
+
+        require!(
+            config.guardians.contains(&guardian),
+            IdentityError::NotAGuardian
+        );
+        require!(
+            !proposal.approvals.contains(&guardian),
+            IdentityError::DuplicateApproval
+        );
+
+        proposal.approvals.push(guardian);
+
+        msg!(
+            "Recovery approved by {} ({}/{})",
+            guardian,
+            proposal.approvals.len(),
+            config.threshold
+        );
+        Ok(())
+    }
+
+    /// Cancel a stale or disputed recovery proposal, freeing the proposal PDA
+    /// so `propose_recovery` can be called again. Callable by the identity
+    /// owner (reasserting control) or any configured guardian (e.g. if the
+    /// proposer named the wrong `new_owner` or guardians can't reach
+    /// threshold).
+    pub fn cancel_recovery(ctx: Context<CancelRecovery>) -> Result<()> {
+        let config = &ctx.accounts.recovery_config;
+        let identity = &ctx.accounts.identity;
+        let canceller = ctx.accounts.canceller.key();
+
+        require!(
+            canceller == identity.owner || config.guardians.contains(&canceller),
+            IdentityError::NotAuthorizedToCancel
+        );
+
+        msg!("Recovery proposal cancelled for identity: {}", identity.owner);
+        Ok(())
+    }
+
+    /// Finalize recovery once the approval threshold and timelock have both
+    /// been satisfied, rotating ownership and clearing the proposal.
+    pub fn finalize_recovery(ctx: Context<FinalizeRecovery>) -> Result<()> {
+        let config = &ctx.accounts.recovery_config;
+        let proposal = &ctx.accounts.proposal;
+        let identity = &mut ctx.accounts.identity;
+        let clock = Clock::get()?;
 
         require!(
-            elapsed >= recovery_period,
-            IdentityError::RecoveryPeriodNotMet
+            proposal.approvals.len() >= config.threshold as usize,
+            IdentityError::ThresholdNotMet
+        );
+        require!(
+            checked_timelock_elapsed(
+                clock.unix_timestamp,
+                proposal.proposed_at,
+                config.timelock_seconds
+            )?,
+            IdentityError::TimelockNotElapsed
         );
 
-        identity.owner = new_owner;
-        identity.recovery_counter += 1;
+        identity.owner = proposal.new_owner;
+        identity.recovery_counter = checked_bump_counter(identity.recovery_counter)?;
         identity.updated_at = clock.unix_timestamp;
 
-        msg!("Identity recovered to new owner: {}", new_owner);
+        msg!("Identity recovered to new owner: {}", identity.owner);
         Ok(())
     }
 }
 
+/// Set `bit_index` in `bits`, checking the index is in range and the shift
+/// doesn't overflow rather than assuming well-formed input.
+fn checked_set_bit(bits: u32, bit_index: u8) -> Result<u32> {
+    require!(bit_index < 32, IdentityError::InvalidBitIndex);
+    let mask = 1u32
+        .checked_shl(bit_index as u32)
+        .ok_or(IdentityError::ArithmeticOverflow)?;
+    Ok(bits | mask)
+}
+
+/// Clear `bit_index` in `bits`, mirroring `checked_set_bit`'s guards.
+fn checked_clear_bit(bits: u32, bit_index: u8) -> Result<u32> {
+    require!(bit_index < 32, IdentityError::InvalidBitIndex);
+    let mask = 1u32
+        .checked_shl(bit_index as u32)
+        .ok_or(IdentityError::ArithmeticOverflow)?;
+    Ok(bits & !mask)
+}
+
+/// Increment a counter, returning `ArithmeticOverflow` instead of panicking
+/// or wrapping at `u64::MAX`.
+fn checked_bump_counter(counter: u64) -> Result<u64> {
+    counter.checked_add(1).ok_or(IdentityError::ArithmeticOverflow.into())
+}
+
+/// Whether `timelock_seconds` have elapsed since `proposed_at`, as of `now`.
+/// Rejects a regressed clock (`now < proposed_at`) instead of letting the
+/// elapsed-time subtraction go negative or overflow.
+fn checked_timelock_elapsed(now: i64, proposed_at: i64, timelock_seconds: i64) -> Result<bool> {
+    require!(now >= proposed_at, IdentityError::ClockRegression);
+    let elapsed = now
+        .checked_sub(proposed_at)
+        .ok_or(IdentityError::ArithmeticOverflow)?;
+    Ok(elapsed >= timelock_seconds)
+}
+
 // ============ Account Structs ============
 
 #[derive(Accounts)]
@@ -167,15 +320,136 @@ pub struct ClearVerificationBit<'info> {
 }
 
 #[derive(Accounts)]
-pub struct RecoverIdentity<'info> {
+pub struct ConfigureGuardians<'info> {
+    #[account(
+        seeds = [b"identity", identity.owner.as_ref()],
+        bump = identity.bump,
+        has_one = owner
+    )]
+    pub identity: Account<'info, IdentityAccount>,
+
+    pub owner: Signer<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + RecoveryConfigAccount::INIT_SPACE,
+        seeds = [b"recovery-config", identity.key().as_ref()],
+        bump
+    )]
+    pub recovery_config: Account<'info, RecoveryConfigAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeRecovery<'info> {
+    #[account(
+        seeds = [b"identity", identity.owner.as_ref()],
+        bump = identity.bump
+    )]
+    pub identity: Account<'info, IdentityAccount>,
+
+    #[account(
+        seeds = [b"recovery-config", identity.key().as_ref()],
+        bump = recovery_config.bump,
+        has_one = identity
+    )]
+    pub recovery_config: Account<'info, RecoveryConfigAccount>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + RecoveryProposalAccount::INIT_SPACE,
+        seeds = [b"recovery-proposal", identity.key().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, RecoveryProposalAccount>,
+
+    pub proposer: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveRecovery<'info> {
+    #[account(
+        seeds = [b"recovery-config", recovery_config.identity.as_ref()],
+        bump = recovery_config.bump
+    )]
+    pub recovery_config: Account<'info, RecoveryConfigAccount>,
+
     #[account(
         mut,
+        seeds = [b"recovery-proposal", recovery_config.identity.as_ref()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, RecoveryProposalAccount>,
+
+    pub guardian: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelRecovery<'info> {
+    #[account(
         seeds = [b"identity", identity.owner.as_ref()],
         bump = identity.bump
     )]
     pub identity: Account<'info, IdentityAccount>,
 
-    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"recovery-config", identity.key().as_ref()],
+        bump = recovery_config.bump,
+        has_one = identity
+    )]
+    pub recovery_config: Account<'info, RecoveryConfigAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"recovery-proposal", identity.key().as_ref()],
+        bump = proposal.bump,
+        has_one = identity,
+        close = canceller
+    )]
+    pub proposal: Account<'info, RecoveryProposalAccount>,
+
+    #[account(mut)]
+    pub canceller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeRecovery<'info> {
+    #[account(
+        mut,
+        seeds = [b"identity", identity.owner.as_ref()],
+        bump = identity.bump
+    )]
+    pub identity: Account<'info, IdentityAccount>,
+
+    #[account(
+        seeds = [b"recovery-config", identity.key().as_ref()],
+        bump = recovery_config.bump,
+        has_one = identity
+    )]
+    pub recovery_config: Account<'info, RecoveryConfigAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"recovery-proposal", identity.key().as_ref()],
+        bump = proposal.bump,
+        has_one = identity,
+        close = closer
+    )]
+    pub proposal: Account<'info, RecoveryProposalAccount>,
+
+    #[account(mut)]
+    pub closer: Signer<'info>,
 }
 
 // ============ State Account ============
@@ -193,6 +467,28 @@ pub struct IdentityAccount {
     pub bump: u8,
 }
 
+#[account]
+#[derive(InitSpace)]
+pub struct RecoveryConfigAccount {
+    pub identity: Pubkey,
+    #[max_len(MAX_GUARDIANS)]
+    pub guardians: Vec<Pubkey>,
+    pub threshold: u8,
+    pub timelock_seconds: i64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct RecoveryProposalAccount {
+    pub identity: Pubkey,
+    pub new_owner: Pubkey,
+    pub proposed_at: i64,
+    #[max_len(MAX_GUARDIANS)]
+    pub approvals: Vec<Pubkey>,
+    pub bump: u8,
+}
+
 // ============ Errors ============
 
 #[error_code]
@@ -203,6 +499,77 @@ pub enum IdentityError {
     #[msg("Invalid bit index (must be 0-31)")]
     InvalidBitIndex,
 
-    #[msg("Recovery period not met (30 days required)")]
-    RecoveryPeriodNotMet,
+    #[msg("At least one guardian is required")]
+    NoGuardians,
+
+    #[msg("Too many guardians (max 10)")]
+    TooManyGuardians,
+
+    #[msg("Threshold must be between 1 and the number of guardians")]
+    InvalidThreshold,
+
+    #[msg("Guardian list contains a duplicate entry")]
+    DuplicateGuardian,
+
+    #[msg("Signer is not a configured guardian")]
+    NotAGuardian,
+
+    #[msg("Guardian has already approved this recovery proposal")]
+    DuplicateApproval,
+
+    #[msg("Recovery proposal has not reached the approval threshold")]
+    ThresholdNotMet,
+
+    #[msg("Recovery timelock has not yet elapsed")]
+    TimelockNotElapsed,
+
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+
+    #[msg("Clock regressed: current time is before the reference timestamp")]
+    ClockRegression,
+
+    #[msg("Only the identity owner or a configured guardian may cancel a recovery proposal")]
+    NotAuthorizedToCancel,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_bit_at_boundary_index_31() {
+        let bits = checked_set_bit(0, 31).unwrap();
+        assert_eq!(bits, 1u32 << 31);
+    }
+
+    #[test]
+    fn set_bit_rejects_out_of_range_index() {
+        assert!(checked_set_bit(0, 32).is_err());
+    }
+
+    #[test]
+    fn clear_bit_at_boundary_index_31() {
+        let bits = checked_clear_bit(u32::MAX, 31).unwrap();
+        assert_eq!(bits, u32::MAX & !(1u32 << 31));
+    }
+
+    #[test]
+    fn bump_counter_overflows_at_u64_max() {
+        assert!(checked_bump_counter(u64::MAX).is_err());
+        assert_eq!(checked_bump_counter(0).unwrap(), 1);
+    }
+
+    #[test]
+    fn timelock_elapsed_at_exact_boundary() {
+        // proposed_at == now: zero elapsed time satisfies a zero timelock...
+        assert!(checked_timelock_elapsed(100, 100, 0).unwrap());
+        // ...but not a positive one.
+        assert!(!checked_timelock_elapsed(100, 100, 1).unwrap());
+    }
+
+    #[test]
+    fn timelock_rejects_clock_regression() {
+        assert!(checked_timelock_elapsed(99, 100, 0).is_err());
+    }
 }